@@ -0,0 +1,60 @@
+use crate::{Selection, SomeElementType};
+use arbitrary::{Arbitrary, Unstructured};
+
+impl<'a> Arbitrary<'a> for SomeElementType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => SomeElementType::Individual(u.arbitrary()?),
+            1 => {
+                let a: usize = u.arbitrary()?;
+                let b: usize = u.arbitrary()?;
+                SomeElementType::Range(a.min(b)..=a.max(b))
+            }
+            2 => SomeElementType::RangeFrom(u.arbitrary()?),
+            _ => SomeElementType::RangeTo(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Selection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Selection::All,
+            1 => Selection::None,
+            // Route through the same condensing `parse`/`FromStr` use, rather than wrapping the
+            // raw elements directly: an un-condensed `Vec` can contain an empty list (which
+            // `Display` renders as `""`, re-parsing as `None`, not `Some([])`) or elements like
+            // `Range(5..=5)` that `Display` renders as `"5-5"` but that re-parse in their reduced
+            // form (`Individual(5)`).
+            _ => crate::to_selection(crate::condense_selections(u.arbitrary()?)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary_selection_is_well_formed() {
+        let data = [0u8; 64];
+        let mut u = Unstructured::new(&data);
+        let _selection = Selection::arbitrary(&mut u).expect("arbitrary data should be usable");
+    }
+
+    #[test]
+    fn arbitrary_selection_round_trips_through_display() {
+        for seed in 0u8..=255 {
+            let data = [seed; 64];
+            let mut u = Unstructured::new(&data);
+            if let Ok(sel) = Selection::arbitrary(&mut u) {
+                let rendered = sel.to_string();
+                assert_eq!(
+                    crate::parse(&rendered).unwrap(),
+                    sel,
+                    "{sel:?} rendered as {rendered:?} did not round-trip"
+                );
+            }
+        }
+    }
+}
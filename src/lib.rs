@@ -1,21 +1,33 @@
 use nom::error::ErrorKind;
+use std::cmp::Ordering;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 /// Parser-related functions
 mod parser;
 
-/// Error type for errors that may arise during the parsing of choice-strings.
-/// Very simple at the moment, only wrapping a nom [`ErrorKind`]
+/// `arbitrary` integration, enabled via the `arbitrary` feature
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+
+/// `proptest` strategies for generating valid choice strings, enabled via the `proptest` feature
+#[cfg(feature = "proptest")]
+pub mod proptest_support;
+
+/// Error type for errors that may arise during the parsing or use of choice-strings.
 #[derive(thiserror::Error, Debug, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum Error {
+    /// Wraps a nom [`ErrorKind`] describing why the input failed to parse
     #[error("Invalid token {}", 0)]
     ParsingFailed(ErrorKind),
+    /// Returned by [`Selection::iter_items`] when the selection has no upper bound
+    #[error("selection is unbounded and cannot be iterated to completion")]
+    Unbounded,
 }
 
 /// A parsed selection. Can represent all, none, or some set of ranges and items.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Selection {
     /// All elements are in the selected set
     All,
@@ -36,6 +48,8 @@ impl Selection {
                         match element {
                             SomeElementType::Individual(num) => item == *num,
                             SomeElementType::Range(range) => range.contains(&item),
+                            SomeElementType::RangeFrom(start) => item >= *start,
+                            SomeElementType::RangeTo(end) => item <= *end,
                         }
                     })
             },
@@ -43,16 +57,191 @@ impl Selection {
             Selection::None => false,
         }
     }
+
+    /// Iterate over every selected index in ascending order, the dual of [`contains_item`].
+    ///
+    /// [`Selection::All`] and any selection containing an open-from range (`RangeFrom`) have no
+    /// upper bound and would iterate forever, so those return [`Error::Unbounded`] instead of
+    /// silently hanging.
+    ///
+    /// [`contains_item`]: Selection::contains_item
+    pub fn iter_items(&self) -> Result<impl Iterator<Item = usize>, Error> {
+        let condensed: Vec<SomeElementType> = match self {
+            Selection::All => return Err(Error::Unbounded),
+            Selection::None => Vec::new(),
+            Selection::Some(v) => condense_selections(v.clone()),
+        };
+        if condensed
+            .iter()
+            .any(|element| matches!(element, SomeElementType::RangeFrom(_)))
+        {
+            return Err(Error::Unbounded);
+        }
+
+        Ok(condensed
+            .into_iter()
+            .flat_map(|element| -> Box<dyn Iterator<Item = usize>> {
+                match element {
+                    SomeElementType::Individual(n) => Box::new(std::iter::once(n)),
+                    SomeElementType::Range(r) => Box::new(r),
+                    SomeElementType::RangeTo(n) => Box::new(0..=n),
+                    SomeElementType::RangeFrom(_) => unreachable!("filtered out above"),
+                }
+            }))
+    }
+
+    /// The set union of this selection and `other`: an item is included if either selection
+    /// includes it.
+    pub fn union(&self, other: &Selection) -> Selection {
+        let mut combined = normalize(self);
+        combined.extend(normalize(other));
+        to_selection(condense_selections(combined))
+    }
+
+    /// The set intersection of this selection and `other`: an item is included only if both
+    /// selections include it.
+    pub fn intersect(&self, other: &Selection) -> Selection {
+        let a = normalize(self);
+        let b = normalize(other);
+        let mut overlaps = Vec::new();
+
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let (start_a, end_a) = element_bounds(&a[i]);
+            let (start_b, end_b) = element_bounds(&b[j]);
+
+            let start = start_a.max(start_b);
+            let end = end_min(end_a, end_b);
+            if end.is_none() || end >= Some(start) {
+                overlaps.push(bounds_to_element(start, end));
+            }
+
+            match cmp_end(end_a, end_b) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        to_selection(condense_selections(overlaps))
+    }
+
+    /// The set complement of this selection: an item is included if and only if this selection
+    /// does not include it. The complement of a selection that stops short of `usize::MAX` ends
+    /// in an open-ended `RangeFrom`.
+    pub fn complement(&self) -> Selection {
+        match self {
+            Selection::All => Selection::None,
+            Selection::None => Selection::All,
+            Selection::Some(v) => match to_selection(condense_selections(v.clone())) {
+                Selection::All => Selection::None,
+                Selection::None => Selection::All,
+                Selection::Some(condensed) => {
+                    let mut gaps = Vec::new();
+                    let mut cursor = 0usize;
+                    let mut reached_infinity = false;
+
+                    for element in &condensed {
+                        let (start, end) = element_bounds(element);
+                        if start > cursor {
+                            gaps.push(bounds_to_element(cursor, Some(start - 1)));
+                        }
+                        match end {
+                            Some(usize::MAX) | None => {
+                                reached_infinity = true;
+                                break;
+                            }
+                            Some(e) => cursor = e + 1,
+                        }
+                    }
+                    if !reached_infinity {
+                        gaps.push(SomeElementType::RangeFrom(cursor));
+                    }
+
+                    to_selection(condense_selections(gaps))
+                }
+            },
+        }
+    }
 }
 
-/// A selected element. Can either be an individual item, or a range of items.
-#[derive(Debug, PartialEq)]
+/// Normalize a [`Selection`] to the condensed element list `condense_selections` produces, so
+/// that `All` and `None` can be combined with `Some` through the same range-merging logic.
+fn normalize(selection: &Selection) -> Vec<SomeElementType> {
+    match selection {
+        Selection::All => vec![SomeElementType::RangeFrom(0)],
+        Selection::None => vec![],
+        Selection::Some(v) => condense_selections(v.clone()),
+    }
+}
+
+/// Turn a condensed element list back into a [`Selection`], recognizing the empty list as
+/// [`Selection::None`] and the `RangeFrom(0)` sentinel (everything covered) as [`Selection::All`].
+fn to_selection(condensed: Vec<SomeElementType>) -> Selection {
+    match condensed.as_slice() {
+        [] => Selection::None,
+        [SomeElementType::RangeFrom(0)] => Selection::All,
+        _ => Selection::Some(condensed),
+    }
+}
+
+/// The inclusive `(start, end)` bounds of an element, with `end` of `None` meaning unbounded
+/// above (only possible for `RangeFrom`, since `usize` has no value below `0`).
+fn element_bounds(element: &SomeElementType) -> (usize, Option<usize>) {
+    match element {
+        SomeElementType::Individual(n) => (*n, Some(*n)),
+        SomeElementType::Range(r) => (*r.start(), Some(*r.end())),
+        SomeElementType::RangeFrom(n) => (*n, None),
+        SomeElementType::RangeTo(n) => (0, Some(*n)),
+    }
+}
+
+/// The inverse of [`element_bounds`].
+fn bounds_to_element(start: usize, end: Option<usize>) -> SomeElementType {
+    match end {
+        None => SomeElementType::RangeFrom(start),
+        Some(end) if end == start => SomeElementType::Individual(start),
+        Some(end) => SomeElementType::Range(start..=end),
+    }
+}
+
+/// The smaller of two optional ends, treating `None` (unbounded) as greater than any `Some`.
+fn end_min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (None, None) => None,
+        (Some(x), None) | (None, Some(x)) => Some(x),
+        (Some(x), Some(y)) => Some(x.min(y)),
+    }
+}
+
+/// Compares two optional ends, treating `None` (unbounded) as greater than any `Some`.
+fn cmp_end(a: Option<usize>, b: Option<usize>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => x.cmp(&y),
+    }
+}
+
+/// A selected element. Can either be an individual item, a bounded range of items, or an
+/// open-ended range extending to the beginning or end of the representable space.
+#[derive(Debug, Clone, PartialEq)]
 pub enum SomeElementType {
     Individual(usize),
     Range(RangeInclusive<usize>),
+    /// Everything from (and including) `usize` upward, e.g. `"5-"`.
+    RangeFrom(usize),
+    /// Everything up to (and including) `usize`, e.g. `"-8"`.
+    RangeTo(usize),
 }
 
 pub use parser::parse as parse_raw;
+pub use parser::parse_with as parse_raw_with;
+pub use parser::ParseOptions;
 
 /// Parse a choice string input to a [`Selection`]. Additionally reduces the set of ranges to the
 /// minimum representable by using a union operation.
@@ -61,41 +250,126 @@ pub fn parse(input: &str) -> Result<Selection, Error> {
     input.parse()
 }
 
+/// Parse a choice string input to a [`Selection`] using custom [`ParseOptions`] (e.g. a different
+/// range delimiter or separator set). Additionally reduces the set of ranges to the minimum
+/// representable by using a union operation, same as [`parse`].
+pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Selection, Error> {
+    match parser::parse_with(input, options)? {
+        Selection::Some(v) => Ok(to_selection(condense_selections(v))),
+        other => Ok(other),
+    }
+}
+
 impl FromStr for Selection {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match parse_raw(s)? {
-            Selection::Some(v) => Selection::Some(condense_selections(v)),
+            Selection::Some(v) => to_selection(condense_selections(v)),
             other => other,
         })
     }
 }
 
+/// Renders this selection as a choice string: `All` as `"all"`, `None` as `"none"`, and `Some` as
+/// its elements joined by spaces, in the order held; no condensing is done here. Since [`FromStr`]
+/// always condenses, a parsed selection renders in its canonical, minimal form and
+/// `parse(s)?.to_string()` is a stable fixed point: parsing it back yields an equal [`Selection`].
+impl std::fmt::Display for Selection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Selection::All => write!(f, "all"),
+            Selection::None => write!(f, "none"),
+            Selection::Some(v) => {
+                let tokens: Vec<String> = v.iter().map(SomeElementType::to_string).collect();
+                write!(f, "{}", tokens.join(" "))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SomeElementType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SomeElementType::Individual(n) => write!(f, "{n}"),
+            SomeElementType::Range(r) => write!(f, "{}-{}", r.start(), r.end()),
+            SomeElementType::RangeFrom(n) => write!(f, "{n}-"),
+            SomeElementType::RangeTo(n) => write!(f, "-{n}"),
+        }
+    }
+}
+
 /// Union the elements together to produce a set of individual elements and ranges that represents
 /// the same set and reduces the amount of elements.
+///
+/// Open-ended elements are folded in after the bounded ones are unioned: a `RangeFrom` absorbs any
+/// bounded range or individual it overlaps or directly abuts, lowering its start to match, and a
+/// `RangeTo` does the same upward. If the two open ends then meet or overlap, every `usize` is
+/// covered and the whole list collapses to the sentinel `RangeFrom(0)`, which [`FromStr`] treats as
+/// equivalent to [`Selection::All`].
 fn condense_selections(selections: Vec<SomeElementType>) -> Vec<SomeElementType> {
+    let mut range_from: Option<usize> = None;
+    let mut range_to: Option<usize> = None;
+
     let mut union = range_union_find::IntRangeUnionFind::new();
     selections
-        .iter()
-        .map(|a| match a {
-            SomeElementType::Individual(num) => *num..=*num,
-            SomeElementType::Range(range) => range.clone(),
+        .into_iter()
+        .filter_map(|a| match a {
+            SomeElementType::Individual(num) => Some(num..=num),
+            SomeElementType::Range(range) => Some(range),
+            SomeElementType::RangeFrom(start) => {
+                range_from = Some(range_from.map_or(start, |prev| prev.min(start)));
+                None
+            }
+            SomeElementType::RangeTo(end) => {
+                range_to = Some(range_to.map_or(end, |prev| prev.max(end)));
+                None
+            }
         })
         .filter(|a| !a.is_empty())
         .try_for_each(|a| union.insert_range(&a))
         .expect("bad ranges - shouldn't happen is bug");
 
-    let v = union.into_collection::<Vec<_>>();
-    v.into_iter()
-        .map(|a| {
-            if a.start() == a.end() {
-                SomeElementType::Individual(*a.start())
-            } else {
-                SomeElementType::Range(a)
+    let mut bounded = union.into_collection::<Vec<_>>();
+
+    if let Some(from) = range_from.as_mut() {
+        while let Some(last) = bounded.last() {
+            if last.end().saturating_add(1) < *from {
+                break;
             }
-        })
-        .collect()
+            *from = (*from).min(*last.start());
+            bounded.pop();
+        }
+    }
+
+    if let Some(to) = range_to.as_mut() {
+        while let Some(first) = bounded.first() {
+            if first.start().saturating_sub(1) > *to {
+                break;
+            }
+            *to = (*to).max(*first.end());
+            bounded.remove(0);
+        }
+    }
+
+    if let (Some(from), Some(to)) = (range_from, range_to) {
+        if from <= to.saturating_add(1) {
+            // The open ends meet or overlap: together they already cover every `usize`.
+            return vec![SomeElementType::RangeFrom(0)];
+        }
+    }
+
+    let mut result = Vec::with_capacity(bounded.len() + 2);
+    result.extend(range_to.map(SomeElementType::RangeTo));
+    result.extend(bounded.into_iter().map(|a| {
+        if a.start() == a.end() {
+            SomeElementType::Individual(*a.start())
+        } else {
+            SomeElementType::Range(a)
+        }
+    }));
+    result.extend(range_from.map(SomeElementType::RangeFrom));
+    result
 }
 
 #[cfg(test)]
@@ -129,6 +403,19 @@ mod helper_tests {
 
     }
 
+    #[test]
+    fn selection_contains_item_open_ranges() {
+        let from = Selection::Some(vec![SomeElementType::RangeFrom(10)]);
+        assert!(!from.contains_item(9));
+        assert!(from.contains_item(10));
+        assert!(from.contains_item(6543268));
+
+        let to = Selection::Some(vec![SomeElementType::RangeTo(10)]);
+        assert!(to.contains_item(0));
+        assert!(to.contains_item(10));
+        assert!(!to.contains_item(11));
+    }
+
     #[test]
     fn condense_ranges() {
         let c = condense_selections(vec![
@@ -168,4 +455,247 @@ mod helper_tests {
             ]
         );
     }
+
+    #[test]
+    fn condense_open_range_from_absorbs_adjoining_elements() {
+        let c = condense_selections(vec![
+            SomeElementType::Individual(1),
+            SomeElementType::Range(5..=9),
+            SomeElementType::RangeFrom(10),
+        ]);
+
+        assert_eq!(
+            c,
+            vec![
+                SomeElementType::Individual(1),
+                SomeElementType::RangeFrom(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn condense_open_range_to_absorbs_adjoining_elements() {
+        let c = condense_selections(vec![
+            SomeElementType::RangeTo(4),
+            SomeElementType::Range(5..=9),
+            SomeElementType::Individual(20),
+        ]);
+
+        assert_eq!(
+            c,
+            vec![SomeElementType::RangeTo(9), SomeElementType::Individual(20)]
+        );
+    }
+
+    #[test]
+    fn condense_open_ranges_meeting_collapse_to_all_sentinel() {
+        let c = condense_selections(vec![
+            SomeElementType::RangeTo(10),
+            SomeElementType::RangeFrom(11),
+        ]);
+
+        assert_eq!(c, vec![SomeElementType::RangeFrom(0)]);
+    }
+
+    #[test]
+    fn parse_bare_dash_is_all() {
+        assert_eq!(parse("-").unwrap(), Selection::All);
+    }
+
+    #[test]
+    fn parse_open_range_from() {
+        assert_eq!(
+            parse("5-").unwrap(),
+            Selection::Some(vec![SomeElementType::RangeFrom(5)])
+        );
+    }
+
+    #[test]
+    fn parse_open_range_to() {
+        assert_eq!(
+            parse("-5").unwrap(),
+            Selection::Some(vec![SomeElementType::RangeTo(5)])
+        );
+    }
+
+    #[test]
+    fn union_with_all_and_none() {
+        let some = parse("1-3").unwrap();
+        assert_eq!(some.union(&Selection::All), Selection::All);
+        assert_eq!(Selection::All.union(&some), Selection::All);
+        assert_eq!(some.union(&Selection::None), some);
+        assert_eq!(Selection::None.union(&Selection::None), Selection::None);
+    }
+
+    #[test]
+    fn union_merges_overlapping_and_adjoining_ranges() {
+        let a = parse("1-5 20").unwrap();
+        let b = parse("6-10 30-40").unwrap();
+        assert_eq!(a.union(&b), parse("1-10 20 30-40").unwrap());
+    }
+
+    #[test]
+    fn union_with_open_ranges() {
+        let a = parse("10-").unwrap();
+        let b = parse("1-5").unwrap();
+        assert_eq!(a.union(&b), parse("1-5 10-").unwrap());
+
+        let c = parse("6-10").unwrap();
+        assert_eq!(a.union(&c), parse("6-").unwrap());
+    }
+
+    #[test]
+    fn intersect_with_all_and_none() {
+        let some = parse("1-3 10").unwrap();
+        assert_eq!(some.intersect(&Selection::All), some);
+        assert_eq!(Selection::All.intersect(&some), some);
+        assert_eq!(some.intersect(&Selection::None), Selection::None);
+    }
+
+    #[test]
+    fn intersect_overlapping_ranges() {
+        let a = parse("1-10 20-30").unwrap();
+        let b = parse("5-25").unwrap();
+        assert_eq!(a.intersect(&b), parse("5-10 20-25").unwrap());
+    }
+
+    #[test]
+    fn intersect_disjoint_ranges_is_none() {
+        let a = parse("1-5").unwrap();
+        let b = parse("6-10").unwrap();
+        assert_eq!(a.intersect(&b), Selection::None);
+    }
+
+    #[test]
+    fn intersect_with_open_ranges() {
+        let a = parse("10-").unwrap();
+        let b = parse("1-20").unwrap();
+        assert_eq!(a.intersect(&b), parse("10-20").unwrap());
+
+        let c = parse("-5").unwrap();
+        assert_eq!(a.intersect(&c), Selection::None);
+    }
+
+    #[test]
+    fn complement_of_all_and_none() {
+        assert_eq!(Selection::All.complement(), Selection::None);
+        assert_eq!(Selection::None.complement(), Selection::All);
+    }
+
+    #[test]
+    fn complement_of_bounded_selection() {
+        let sel = parse("5-10 20").unwrap();
+        assert_eq!(
+            sel.complement(),
+            Selection::Some(vec![
+                SomeElementType::Range(0..=4),
+                SomeElementType::Range(11..=19),
+                SomeElementType::RangeFrom(21),
+            ])
+        );
+    }
+
+    #[test]
+    fn complement_is_its_own_inverse() {
+        let sel = parse("1-3 8 12-").unwrap();
+        assert_eq!(sel.complement().complement(), sel);
+    }
+
+    #[test]
+    fn complement_of_range_ending_at_max_excludes_max() {
+        let sel = parse(&format!("5-{}", usize::MAX)).unwrap();
+        assert!(sel.contains_item(usize::MAX));
+        assert!(!sel.complement().contains_item(usize::MAX));
+    }
+
+    #[test]
+    fn complement_of_open_to_max_excludes_max() {
+        let sel = parse(&format!("-{}", usize::MAX)).unwrap();
+        assert!(sel.contains_item(usize::MAX));
+        assert!(!sel.complement().contains_item(usize::MAX));
+    }
+
+    #[test]
+    fn display_all_and_none() {
+        assert_eq!(Selection::All.to_string(), "all");
+        assert_eq!(Selection::None.to_string(), "none");
+    }
+
+    #[test]
+    fn display_individuals_and_ranges() {
+        assert_eq!(
+            Selection::Some(vec![
+                SomeElementType::Individual(1),
+                SomeElementType::Range(3..=5),
+            ])
+            .to_string(),
+            "1 3-5"
+        );
+    }
+
+    #[test]
+    fn display_open_ranges() {
+        assert_eq!(
+            Selection::Some(vec![SomeElementType::RangeFrom(5)]).to_string(),
+            "5-"
+        );
+        assert_eq!(
+            Selection::Some(vec![SomeElementType::RangeTo(8)]).to_string(),
+            "-8"
+        );
+    }
+
+    #[test]
+    fn display_parse_round_trip_is_a_fixed_point() {
+        for input in [
+            "all",
+            "none",
+            "1 3 5-9 20",
+            "5-",
+            "-8",
+            "1-5 10-",
+            "-4 11-19 21-",
+        ] {
+            let sel = parse(input).unwrap();
+            let rendered = sel.to_string();
+            assert_eq!(parse(&rendered).unwrap(), sel, "round-trip of {input:?}");
+        }
+    }
+
+    #[test]
+    fn iter_items_some() {
+        let sel = parse("1 3 5-8").unwrap();
+        assert_eq!(
+            sel.iter_items().unwrap().collect::<Vec<_>>(),
+            vec![1, 3, 5, 6, 7, 8]
+        );
+    }
+
+    #[test]
+    fn iter_items_none_is_empty() {
+        assert_eq!(
+            Selection::None.iter_items().unwrap().collect::<Vec<_>>(),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn iter_items_open_to_is_finite() {
+        let sel = parse("-3").unwrap();
+        assert_eq!(
+            sel.iter_items().unwrap().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn iter_items_all_is_unbounded_error() {
+        assert_eq!(Selection::All.iter_items().err(), Some(Error::Unbounded));
+    }
+
+    #[test]
+    fn iter_items_open_from_is_unbounded_error() {
+        let sel = parse("5-").unwrap();
+        assert_eq!(sel.iter_items().err(), Some(Error::Unbounded));
+    }
 }
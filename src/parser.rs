@@ -1,7 +1,7 @@
 use crate::{Selection, SomeElementType};
 use nom::branch::alt;
-use nom::bytes::complete::{is_a, tag, tag_no_case};
-use nom::character::complete::{digit1, space1};
+use nom::bytes::complete::{is_a, tag_no_case};
+use nom::character::complete::{char as char1, digit1, one_of};
 
 use nom::combinator::{complete, cut, eof, map, map_res};
 
@@ -11,6 +11,53 @@ use nom::IResult;
 
 use std::str::FromStr;
 
+/// Options controlling how a choice string is tokenized. Built with the builder methods below;
+/// [`ParseOptions::default`] matches the separators and range delimiter [`crate::parse`] uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    range_delimiter: char,
+    separators: Vec<char>,
+    allow_empty_tokens: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            range_delimiter: '-',
+            separators: vec![',', ';', ' ', '\t'],
+            allow_empty_tokens: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// The default options: `-` as the range delimiter, `,`/`;`/whitespace as separators, and
+    /// empty tokens (e.g. repeated separators) tolerated.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `delimiter` instead of `-` to separate the start and end of a range, e.g. `:` to parse
+    /// `"5:8"` like field-selection tools do.
+    pub fn with_range_delimiter(mut self, delimiter: char) -> Self {
+        self.range_delimiter = delimiter;
+        self
+    }
+
+    /// Use `separators` instead of the default `,`, `;`, space, and tab to separate elements.
+    pub fn with_separators(mut self, separators: impl IntoIterator<Item = char>) -> Self {
+        self.separators = separators.into_iter().collect();
+        self
+    }
+
+    /// Whether runs of repeated separators (e.g. `"1,,5"`) are tolerated. Defaults to `true`; set
+    /// to `false` to require exactly one separator between elements.
+    pub fn allow_empty_tokens(mut self, allow: bool) -> Self {
+        self.allow_empty_tokens = allow;
+        self
+    }
+}
+
 /// Selects `/(none)?$/`
 fn select_none(input: &str) -> IResult<&str, Selection> {
     fn none_literal(input: &str) -> IResult<&str, Selection> {
@@ -19,44 +66,88 @@ fn select_none(input: &str) -> IResult<&str, Selection> {
     alt((map(eof, |_| Selection::None), none_literal))(input)
 }
 
-/// Selects `/^((([0-9]+-[0-9]+)|([0-9]+))( ;,)*)+$/`
-fn select_some(input: &str) -> IResult<&str, Selection> {
-    /// The (last) `/([0-9]+)/` part
-    fn individual_element(input: &str) -> IResult<&str, SomeElementType> {
-        map(
-            map_res(digit1, usize::from_str),
-            SomeElementType::Individual,
-        )(input)
-    }
-    /// The `/([0-9]+-[0-9]+)/` part
-    fn range_element(input: &str) -> IResult<&str, SomeElementType> {
-        map(
+/// The (last) `/([0-9]+)/` part of [`select_some`]
+fn individual_element(input: &str) -> IResult<&str, SomeElementType> {
+    map(
+        map_res(digit1, usize::from_str),
+        SomeElementType::Individual,
+    )(input)
+}
+
+/// Selects `/^((([0-9]+D[0-9]+)|([0-9]+D)|(D[0-9]+)|D|([0-9]+))(S+|$))+$/`, where `D` is the
+/// configured range delimiter and `S` is the configured separator set (a single `S` in place of
+/// `S+` when empty tokens are not allowed)
+fn select_some(options: &ParseOptions) -> impl Fn(&str) -> IResult<&str, Selection> + '_ {
+    move |input: &str| {
+        let delimiter = options.range_delimiter;
+
+        // The `/([0-9]+D[0-9]+)/` part
+        let full_range = |input| -> IResult<&str, SomeElementType> {
+            map(
+                tuple((
+                    map_res(digit1, usize::from_str),
+                    char1(delimiter),
+                    map_res(digit1, usize::from_str),
+                )),
+                |(start, _, end)| SomeElementType::Range(start..=end),
+            )(input)
+        };
+        // The `/([0-9]+D)/` part: an open-ended range from `start` upward, e.g. `"5-"`
+        let range_from = |input| -> IResult<&str, SomeElementType> {
+            map(
+                tuple((map_res(digit1, usize::from_str), char1(delimiter))),
+                |(start, _)| SomeElementType::RangeFrom(start),
+            )(input)
+        };
+        // The `/(D[0-9]+)/` part: an open-ended range up to `end`, e.g. `"-8"`
+        let range_to = |input| -> IResult<&str, SomeElementType> {
+            map(
+                tuple((char1(delimiter), map_res(digit1, usize::from_str))),
+                |(_, end)| SomeElementType::RangeTo(end),
+            )(input)
+        };
+        // A bare `/D/`, equivalent to selecting everything
+        let bare_delimiter = |input| -> IResult<&str, SomeElementType> {
+            map(char1(delimiter), |_| SomeElementType::RangeFrom(0))(input)
+        };
+        // The `/(([0-9]+D[0-9]+)|([0-9]+D)|(D[0-9]+)|D)/` part
+        let range_element = |input| alt((full_range, range_from, range_to, bare_delimiter))(input);
+        // The `/(([0-9]+D[0-9]+)|([0-9]+D)|(D[0-9]+)|D|([0-9]+))/` part
+        let some_element = |input| alt((range_element, individual_element))(input);
+
+        let separator_chars: String = options.separators.iter().collect();
+        let allow_empty_tokens = options.allow_empty_tokens;
+        // The `/(S+|$)/` part, or `/(S|$)/` when empty tokens are not allowed
+        fn element_separator<'a>(
+            separator_chars: &str,
+            allow_empty_tokens: bool,
+            input: &'a str,
+        ) -> IResult<&'a str, ()> {
+            if allow_empty_tokens {
+                alt((map(is_a(separator_chars), |_| ()), map(eof, |_| ())))(input)
+            } else {
+                alt((map(one_of(separator_chars), |_| ()), map(eof, |_| ())))(input)
+            }
+        }
+        let element_separator =
+            |input| element_separator(&separator_chars, allow_empty_tokens, input);
+
+        // `map(...)(input)` is bound to a variable before being returned, rather than returned
+        // directly, so that the temporary parser (which borrows the locals above) is dropped
+        // before those locals go out of scope at the end of the block.
+        #[allow(clippy::let_and_return)]
+        let result = map(
             tuple((
-                map_res(digit1, usize::from_str),
-                is_a("-"),
-                map_res(cut(digit1), usize::from_str),
+                many1(map(
+                    tuple((some_element, element_separator)),
+                    |(etype, _rem)| etype,
+                )),
+                eof,
             )),
-            |(start, _, end)| SomeElementType::Range(start..=end),
-        )(input)
+            |a| Selection::Some(a.0),
+        )(input);
+        result
     }
-    /// The `/(([0-9]+-[0-9]+)|([0-9]+))/` part
-    fn some_element(input: &str) -> IResult<&str, SomeElementType> {
-        alt((range_element, individual_element))(input)
-    }
-    /// The `/( ;,)*/` part
-    fn element_separator(input: &str) -> IResult<&str, &str> {
-        alt((map(many1(alt((tag(","), tag(";"), space1))), |_| ""), eof))(input)
-    }
-    map(
-        tuple((
-            many1(map(
-                tuple((some_element, element_separator)),
-                |(etype, _rem)| etype,
-            )),
-            eof,
-        )),
-        |a| Selection::Some(a.0),
-    )(input)
 }
 
 /// Selects `/all$/`
@@ -64,26 +155,31 @@ fn select_all(input: &str) -> IResult<&str, Selection> {
     map(tuple((tag_no_case("all"), cut(eof))), |_| Selection::All)(input)
 }
 
-/// Parses the full selection
-fn selection(input: &str) -> IResult<&str, Selection> {
-    complete(alt((select_none, select_all, select_some)))(input)
+/// Parses the full selection according to `options`
+fn selection(options: &ParseOptions) -> impl Fn(&str) -> IResult<&str, Selection> + '_ {
+    move |input: &str| complete(alt((select_none, select_all, select_some(options))))(input)
 }
 
-/// Parses a choice string to a [`Selection`]. This does not do any de-duplicating or condensing of
-/// parsed ranges.
-pub fn parse(input: &str) -> Result<Selection, crate::Error> {
-    match selection(input) {
+/// Parses a choice string to a [`Selection`] using custom [`ParseOptions`]. This does not do any
+/// de-duplicating or condensing of parsed ranges.
+pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Selection, crate::Error> {
+    match selection(options)(input) {
         Ok((_, sel)) => Ok(sel),
-        Err(err) => {
-            if let nom::Err::Failure(error) = err {
-                Err(crate::Error::ParsingFailed(error.code))
-            } else {
-                panic!("Internal parser error");
-            }
+        // `selection` is built entirely from `_complete` parsers, so it never reports
+        // `Incomplete`; both `Error` and `Failure` mean the input is not a valid choice string.
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(crate::Error::ParsingFailed(error.code))
         }
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never report Incomplete"),
     }
 }
 
+/// Parses a choice string to a [`Selection`] using the default [`ParseOptions`]. This does not do
+/// any de-duplicating or condensing of parsed ranges.
+pub fn parse(input: &str) -> Result<Selection, crate::Error> {
+    parse_with(input, &ParseOptions::default())
+}
+
 #[cfg(test)]
 mod parser_tests {
 
@@ -94,7 +190,7 @@ mod parser_tests {
         ($input:literal, $name:ident) => {
             #[test]
             fn $name() {
-                selection($input).unwrap();
+                selection(&ParseOptions::default())($input).unwrap();
             }
         };
     }
@@ -120,19 +216,33 @@ mod parser_tests {
 
     does_parse!("1-10 15 20", mixed_elements);
 
+    does_parse!("1-", open_range_from);
+    does_parse!("-5", open_range_to);
+    does_parse!("-", open_range_bare);
+    does_parse!("1 5- 20", mixed_with_open_range_from);
+
     #[test]
-    fn fails_broken_range_start() {
-        selection("1-").unwrap_err();
+    fn content_open_range_from() {
+        assert_eq!(
+            parse("5-").unwrap(),
+            Selection::Some(vec![SomeElementType::RangeFrom(5)])
+        );
     }
 
     #[test]
-    fn fails_broken_range_both() {
-        selection("-").unwrap_err();
+    fn content_open_range_to() {
+        assert_eq!(
+            parse("-8").unwrap(),
+            Selection::Some(vec![SomeElementType::RangeTo(8)])
+        );
     }
 
     #[test]
-    fn fails_broken_range_end() {
-        selection("-5").unwrap_err();
+    fn content_open_range_bare() {
+        assert_eq!(
+            parse("-").unwrap(),
+            Selection::Some(vec![SomeElementType::RangeFrom(0)])
+        );
     }
 
     #[test]
@@ -184,11 +294,47 @@ mod parser_tests {
 
     #[test]
     fn test_error() {
-        let err = parse("1 3 5 6-8 1-;455").unwrap_err();
+        let err = parse("allison").unwrap_err();
         match err {
-            crate::Error::ParsingFailed(kind) => assert_eq!(kind, ErrorKind::Digit),
+            crate::Error::ParsingFailed(kind) => assert_eq!(kind, ErrorKind::Eof),
             #[allow(unreachable_patterns)]
             _ => panic!("Wrong kind"),
         }
     }
+
+    #[test]
+    fn custom_range_delimiter() {
+        let options = ParseOptions::new().with_range_delimiter(':');
+        assert_eq!(
+            parse_with("5:8 10", &options).unwrap(),
+            Selection::Some(vec![
+                SomeElementType::Range(5..=8),
+                SomeElementType::Individual(10)
+            ])
+        );
+        // the default delimiter is no longer special once overridden
+        parse_with("5-8", &options).unwrap_err();
+    }
+
+    #[test]
+    fn custom_separators() {
+        let options = ParseOptions::new().with_separators(['|']);
+        assert_eq!(
+            parse_with("1|2|3-5", &options).unwrap(),
+            Selection::Some(vec![
+                SomeElementType::Individual(1),
+                SomeElementType::Individual(2),
+                SomeElementType::Range(3..=5),
+            ])
+        );
+        // the default separators are no longer special once overridden
+        parse_with("1,2", &options).unwrap_err();
+    }
+
+    #[test]
+    fn disallowing_empty_tokens_rejects_repeated_separators() {
+        let options = ParseOptions::new().allow_empty_tokens(false);
+        parse_with("1,5,8", &options).unwrap();
+        parse_with("1,,5,8", &options).unwrap_err();
+    }
 }
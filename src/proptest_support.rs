@@ -0,0 +1,48 @@
+use crate::Selection;
+use proptest::prelude::*;
+
+/// A single element token: an individual, a bounded range, or an open-ended range, as accepted by
+/// the parser's grammar.
+fn element_token() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<u16>().prop_map(|n| n.to_string()),
+        (any::<u16>(), any::<u16>()).prop_map(|(a, b)| format!("{}-{}", a.min(b), a.max(b))),
+        any::<u16>().prop_map(|n| format!("{n}-")),
+        any::<u16>().prop_map(|n| format!("-{n}")),
+    ]
+}
+
+/// A separator the parser accepts between elements: `,`, `;`, or one-or-more spaces.
+fn separator_token() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(",".to_string()),
+        Just(";".to_string()),
+        (1..4usize).prop_map(|n| " ".repeat(n)),
+    ]
+}
+
+/// Generates an arbitrary valid choice string: `"all"`, `"none"`, or a list of individuals and
+/// ranges joined by the `,`/`;`/space separators the parser accepts.
+pub fn choice_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("all".to_string()),
+        Just("none".to_string()),
+        prop::collection::vec(element_token(), 1..8).prop_flat_map(|elements| {
+            prop::collection::vec(separator_token(), elements.len() - 1).prop_map(
+                move |separators| {
+                    let mut rendered = elements[0].clone();
+                    for (element, separator) in elements[1..].iter().zip(separators) {
+                        rendered.push_str(&separator);
+                        rendered.push_str(element);
+                    }
+                    rendered
+                },
+            )
+        }),
+    ]
+}
+
+/// Generates an arbitrary [`Selection`] by parsing a generated [`choice_string`].
+pub fn selection() -> impl Strategy<Value = Selection> {
+    choice_string().prop_map(|s| crate::parse(&s).expect("generated choice string must parse"))
+}
@@ -0,0 +1,16 @@
+#![cfg(all(feature = "arbitrary", feature = "proptest"))]
+
+use arbitrary::{Arbitrary, Unstructured};
+use choice_string::Selection;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn arbitrary_derived_selection_round_trips_through_display(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+        let mut u = Unstructured::new(&bytes);
+        if let Ok(sel) = Selection::arbitrary(&mut u) {
+            let rendered = sel.to_string();
+            prop_assert_eq!(choice_string::parse(&rendered).unwrap(), sel);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "proptest")]
+
+use choice_string::proptest_support::{choice_string, selection};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn display_parse_round_trips(sel in selection()) {
+        let rendered = sel.to_string();
+        prop_assert_eq!(choice_string::parse(&rendered).unwrap(), sel);
+    }
+
+    #[test]
+    fn condensing_a_rendered_selection_is_idempotent(sel in selection()) {
+        let once = choice_string::parse(&sel.to_string()).unwrap();
+        let twice = choice_string::parse(&once.to_string()).unwrap();
+        prop_assert_eq!(once.to_string(), twice.to_string());
+
+        for probe in [0usize, 1, 2, 5, 10, 100, 1000] {
+            prop_assert_eq!(sel.contains_item(probe), twice.contains_item(probe));
+        }
+    }
+
+    #[test]
+    fn generated_choice_strings_always_parse(s in choice_string()) {
+        prop_assert!(choice_string::parse(&s).is_ok());
+    }
+
+    #[test]
+    fn parser_never_panics_on_arbitrary_input(s in ".*") {
+        let _ = choice_string::parse(&s);
+    }
+}